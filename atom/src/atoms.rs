@@ -0,0 +1,5 @@
+//! Atom types defined by the LV2 atom extension.
+mod chunk;
+pub mod scalar;
+
+pub use chunk::{Chunk, ChunkReadHandle, ChunkWriteHandle, ChunkWriter};
@@ -0,0 +1,82 @@
+//! An atom containing a raw, unspecified chunk of memory.
+use crate::space::{AtomSpace, AtomSpaceWriter, Space};
+use crate::{Atom, AtomHandle};
+use urid::UriBound;
+
+/// An atom that contains raw, untyped memory.
+///
+/// Corresponds to the `atom:Chunk` type, the opaque "raw bytes of unspecified type" atom used
+/// to carry arbitrary binary payloads: sample data, serialized blobs, or framed sub-buffers
+/// nested inside a bigger atom. Unlike scalar atoms, a chunk's size is not fixed up front; its
+/// write handle lets a plugin append bytes incrementally, growing the atom header's `size`
+/// field as it goes.
+pub struct Chunk;
+
+unsafe impl UriBound for Chunk {
+    const URI: &'static [u8] = lv2_sys::LV2_ATOM__Chunk;
+}
+
+/// Handle to read a [`Chunk`] atom's body as a byte slice.
+pub struct ChunkReadHandle<'a>(std::marker::PhantomData<&'a ()>);
+
+impl<'a> AtomHandle<'a> for ChunkReadHandle<'a> {
+    type Handle = &'a [u8];
+}
+
+/// Handle to append bytes to a [`Chunk`] atom while it is being written.
+pub struct ChunkWriteHandle<'a>(std::marker::PhantomData<&'a mut ()>);
+
+impl<'a> AtomHandle<'a> for ChunkWriteHandle<'a> {
+    type Handle = ChunkWriter<'a>;
+}
+
+impl<'a> Atom<'a> for Chunk {
+    type ReadHandle = ChunkReadHandle<'a>;
+    type WriteHandle = ChunkWriteHandle<'a>;
+
+    unsafe fn read(body: &'a AtomSpace) -> Option<&'a [u8]> {
+        Some(body.as_bytes())
+    }
+
+    fn init(writer: AtomSpaceWriter<'a>) -> Option<ChunkWriter<'a>> {
+        Some(ChunkWriter { writer })
+    }
+}
+
+/// Write handle for a [`Chunk`] atom, returned by [`Atom::init`].
+///
+/// Every call to [`append`](Self::append) grows the chunk by the given number of bytes and
+/// updates the atom header's recorded size accordingly.
+pub struct ChunkWriter<'a> {
+    writer: AtomSpaceWriter<'a>,
+}
+
+impl<'a> ChunkWriter<'a> {
+    /// Append `data` to the chunk's body, returning a mutable view of the written bytes.
+    ///
+    /// Returns `None` if the host-provided buffer does not have enough remaining space to
+    /// hold `data`.
+    pub fn append(&mut self, data: &[u8]) -> Option<&mut [u8]> {
+        self.writer.write_bytes(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_returns_the_whole_body_as_bytes() {
+        let bytes = [1u8, 2, 3, 4];
+        let space = AtomSpace::from_bytes(&bytes);
+        let read = unsafe { Chunk::read(space) }.unwrap();
+        assert_eq!(read, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn read_returns_an_empty_slice_for_an_empty_body() {
+        let space = AtomSpace::from_bytes(&[]);
+        let read = unsafe { Chunk::read(space) }.unwrap();
+        assert!(read.is_empty());
+    }
+}
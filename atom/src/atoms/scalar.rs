@@ -0,0 +1,95 @@
+//! Scalar atom types backed by a single, fixed-size, plain-old-data value.
+use crate::space::{AtomSpace, AtomSpaceWriter, Space};
+use crate::{Atom, AtomAsBytes, AtomHandle};
+use urid::UriBound;
+
+/// Handle to read a scalar atom's value, borrowed straight out of its atom space.
+pub struct ScalarReadHandle<'a, T>(std::marker::PhantomData<&'a T>);
+
+impl<'a, T: 'a> AtomHandle<'a> for ScalarReadHandle<'a, T> {
+    type Handle = &'a T;
+}
+
+/// Handle to write a scalar atom's value while it is being initialized.
+pub struct ScalarWriteHandle<'a, T>(std::marker::PhantomData<&'a mut T>);
+
+impl<'a, T: 'a> AtomHandle<'a> for ScalarWriteHandle<'a, T> {
+    type Handle = &'a mut T;
+}
+
+macro_rules! make_scalar_atom {
+    ($ty:ident, $repr:ty, $uri:expr) => {
+        #[doc = concat!("The `", $uri, "` atom, a single `", stringify!($repr), "` value.")]
+        pub struct $ty;
+
+        unsafe impl UriBound for $ty {
+            const URI: &'static [u8] = $uri;
+        }
+
+        unsafe impl AtomAsBytes for $ty {}
+
+        impl<'a> Atom<'a> for $ty {
+            type ReadHandle = ScalarReadHandle<'a, $repr>;
+            type WriteHandle = ScalarWriteHandle<'a, $repr>;
+
+            unsafe fn read(body: &'a AtomSpace) -> Option<&'a $repr> {
+                body.as_bytes_of_size(core::mem::size_of::<$repr>())
+                    .map(|bytes| &*(bytes.as_ptr() as *const $repr))
+            }
+
+            fn init(mut writer: AtomSpaceWriter<'a>) -> Option<&'a mut $repr> {
+                let bytes = writer.write_bytes(&<$repr>::default().to_ne_bytes())?;
+                Some(unsafe { &mut *(bytes.as_mut_ptr() as *mut $repr) })
+            }
+        }
+    };
+}
+
+make_scalar_atom!(Int, i32, lv2_sys::LV2_ATOM__Int);
+make_scalar_atom!(Long, i64, lv2_sys::LV2_ATOM__Long);
+make_scalar_atom!(Float, f32, lv2_sys::LV2_ATOM__Float);
+make_scalar_atom!(Double, f64, lv2_sys::LV2_ATOM__Double);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int_reads_back_its_bytes() {
+        let bytes = 42i32.to_ne_bytes();
+        let space = AtomSpace::from_bytes(&bytes);
+        let value = unsafe { Int::read(space) }.unwrap();
+        assert_eq!(*value, 42);
+    }
+
+    #[test]
+    fn long_reads_back_its_bytes() {
+        let bytes = 42i64.to_ne_bytes();
+        let space = AtomSpace::from_bytes(&bytes);
+        let value = unsafe { Long::read(space) }.unwrap();
+        assert_eq!(*value, 42);
+    }
+
+    #[test]
+    fn float_reads_back_its_bytes() {
+        let bytes = 4.5f32.to_ne_bytes();
+        let space = AtomSpace::from_bytes(&bytes);
+        let value = unsafe { Float::read(space) }.unwrap();
+        assert_eq!(*value, 4.5);
+    }
+
+    #[test]
+    fn double_reads_back_its_bytes() {
+        let bytes = 4.5f64.to_ne_bytes();
+        let space = AtomSpace::from_bytes(&bytes);
+        let value = unsafe { Double::read(space) }.unwrap();
+        assert_eq!(*value, 4.5);
+    }
+
+    #[test]
+    fn read_fails_when_the_body_is_too_small() {
+        let bytes = [0u8; 2];
+        let space = AtomSpace::from_bytes(&bytes);
+        assert!(unsafe { Int::read(space) }.is_none());
+    }
+}
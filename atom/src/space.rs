@@ -9,6 +9,6 @@ mod vec;
 
 pub use allocatable::*;
 pub use atom_writer::AtomSpaceWriter;
-pub use cursor::SpaceCursor;
+pub use cursor::{SpaceCursor, SpaceSavepoint};
 pub use space::{AtomSpace, Space};
-pub use vec::{VecSpace, VecSpaceCursor};
+pub use vec::{VecSavepoint, VecSpace, VecSpaceCursor};
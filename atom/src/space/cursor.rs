@@ -0,0 +1,151 @@
+//! A cursor that writes atoms into a fixed, host-provided buffer.
+use core::marker::PhantomData;
+
+/// A checkpoint of a [`SpaceCursor`]'s write head, taken with [`SpaceCursor::savepoint`] and
+/// restored with [`SpaceCursor::truncate`].
+///
+/// `'id` is an invariant lifetime minted fresh by [`SpaceCursor::with`] for each cursor, so it
+/// cannot unify with another cursor's `'id` even when both cursors share the same buffer
+/// lifetime `'a`. Passing a savepoint to any cursor other than the one that produced it -
+/// whether a [`VecSpaceCursor`](super::vec::VecSpaceCursor), an unrelated `SpaceCursor`, or
+/// even another `SpaceCursor` over the very same buffer - is therefore a type error rather
+/// than a runtime panic.
+#[derive(Clone, Copy)]
+pub struct SpaceSavepoint<'a, 'id> {
+    head: usize,
+    _cursor: PhantomData<&'a mut ()>,
+    _id: PhantomData<fn(&'id ()) -> &'id ()>,
+}
+
+/// Writes atoms into a fixed-size buffer, one after another, keeping track of the write head.
+///
+/// This is the allocator backing [`AtomSpaceWriter`](crate::space::AtomSpaceWriter) when
+/// writing directly into a host-provided buffer, such as the one behind an `atom:AtomPort`.
+/// Since the buffer cannot grow, building a nested atom tree (an Object containing a Tuple
+/// containing a Vector, say) needs a way to speculatively write a child and back out if it
+/// turns out not to fit; [`savepoint`](Self::savepoint) and
+/// [`truncate`](Self::truncate) provide that.
+///
+/// The `'id` parameter is an invariant lifetime, unique to each cursor, that brands the
+/// [`SpaceSavepoint`]s this cursor produces so they can't be replayed against a different
+/// cursor. It has no meaning beyond that and is never observed; cursors are created through
+/// [`with`](Self::with), which mints a fresh `'id` for each call.
+pub struct SpaceCursor<'a, 'id> {
+    data: &'a mut [u8],
+    head: usize,
+    _id: PhantomData<fn(&'id ()) -> &'id ()>,
+}
+
+impl<'a> SpaceCursor<'a, '_> {
+    /// Run `f` with a cursor writing into `data`, starting at its beginning.
+    ///
+    /// The cursor is branded with an `'id` unique to this call, so its savepoints can never be
+    /// confused with those of any other cursor, even another `SpaceCursor` over the same
+    /// buffer.
+    pub fn with<R>(data: &'a mut [u8], f: impl for<'id> FnOnce(SpaceCursor<'a, 'id>) -> R) -> R {
+        f(SpaceCursor {
+            data,
+            head: 0,
+            _id: PhantomData,
+        })
+    }
+}
+
+impl<'a, 'id> SpaceCursor<'a, 'id> {
+    /// Number of bytes still available past the write head.
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.head
+    }
+
+    /// Append `bytes` and pad the write head up to the next 8-byte atom alignment boundary,
+    /// or up to the end of the buffer if that comes first.
+    ///
+    /// Returns `None`, leaving the cursor untouched, if `bytes` does not fit in the
+    /// remaining space. The padding bytes are zeroed, since the buffer may be reused from a
+    /// previous `run()` call and still hold stale data.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Option<&mut [u8]> {
+        if bytes.len() > self.remaining() {
+            return None;
+        }
+        let start = self.head;
+        self.data[start..start + bytes.len()].copy_from_slice(bytes);
+        self.head += bytes.len();
+        let padding = self.padding().min(self.remaining());
+        for byte in &mut self.data[self.head..self.head + padding] {
+            *byte = 0;
+        }
+        self.head += padding;
+        Some(&mut self.data[start..start + bytes.len()])
+    }
+
+    fn padding(&self) -> usize {
+        (8 - self.head % 8) % 8
+    }
+
+    /// Capture the current write head so a speculative write can be rolled back later.
+    pub fn savepoint(&self) -> SpaceSavepoint<'a, 'id> {
+        SpaceSavepoint {
+            head: self.head,
+            _cursor: PhantomData,
+            _id: PhantomData,
+        }
+    }
+
+    /// Reset the write head back to `savepoint`, discarding everything written since
+    /// (including any alignment padding consumed past it) without touching the bytes before
+    /// it.
+    pub fn truncate(&mut self, savepoint: SpaceSavepoint<'a, 'id>) {
+        self.head = savepoint.head;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_bytes_advances_head_and_pads_to_alignment() {
+        let mut buffer = [0xffu8; 16];
+        SpaceCursor::with(&mut buffer, |mut cursor| {
+            assert_eq!(cursor.remaining(), 16);
+            cursor.write_bytes(&[1, 2, 3]).unwrap();
+            assert_eq!(cursor.remaining(), 8);
+        });
+    }
+
+    #[test]
+    fn write_bytes_zeroes_alignment_padding() {
+        let mut buffer = [0xffu8; 8];
+        SpaceCursor::with(&mut buffer, |mut cursor| {
+            cursor.write_bytes(&[1, 2, 3]).unwrap();
+        });
+        assert_eq!(&buffer, &[1, 2, 3, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn write_bytes_fails_when_buffer_is_too_small() {
+        let mut buffer = [0u8; 4];
+        SpaceCursor::with(&mut buffer, |mut cursor| {
+            assert!(cursor.write_bytes(&[1, 2, 3, 4, 5]).is_none());
+            assert_eq!(cursor.remaining(), 4);
+        });
+    }
+
+    #[test]
+    fn truncate_rolls_back_to_a_savepoint() {
+        let mut buffer = [0u8; 16];
+        SpaceCursor::with(&mut buffer, |mut cursor| {
+            cursor.write_bytes(&[1, 2, 3]).unwrap();
+            let savepoint = cursor.savepoint();
+            cursor.write_bytes(&[4, 5, 6]).unwrap();
+            assert_eq!(cursor.remaining(), 0);
+            cursor.truncate(savepoint);
+            assert_eq!(cursor.remaining(), 8);
+        });
+    }
+
+    // A savepoint taken from one `SpaceCursor::with` call cannot be passed to another: each
+    // call mints its own invariant `'id`, so e.g. `SpaceCursor::with(&mut a, |mut ca| {
+    // SpaceCursor::with(&mut b, |mut cb| cb.truncate(ca.savepoint())) })` fails to compile
+    // with a lifetime mismatch, rather than panicking or corrupting `cb`'s write head.
+}
@@ -0,0 +1,119 @@
+//! A growable, `Vec`-backed atom space.
+use core::marker::PhantomData;
+
+/// A checkpoint of a [`VecSpaceCursor`]'s length, taken with [`VecSpaceCursor::savepoint`] and
+/// restored with [`VecSpaceCursor::truncate`].
+///
+/// `'id` is an invariant lifetime minted fresh by [`VecSpace::with_cursor`] for each cursor,
+/// so it cannot unify with another cursor's `'id` even when both cursors borrow the same
+/// `VecSpace`. Passing a savepoint to any cursor other than the one that produced it -
+/// whether a [`SpaceCursor`](super::cursor::SpaceCursor), an unrelated `VecSpaceCursor`, or
+/// even another `VecSpaceCursor` over the very same space - is therefore a type error rather
+/// than a runtime panic.
+#[derive(Clone, Copy)]
+pub struct VecSavepoint<'a, 'id> {
+    len: usize,
+    _cursor: PhantomData<&'a mut ()>,
+    _id: PhantomData<fn(&'id ()) -> &'id ()>,
+}
+
+/// An atom space backed by a growable `Vec<u8>`, rather than a fixed host buffer.
+///
+/// Useful for building atoms off the real-time thread, or for tests, where there is no
+/// pre-allocated buffer to write into.
+pub struct VecSpace {
+    data: Vec<u8>,
+}
+
+impl VecSpace {
+    /// Create an empty, growable atom space.
+    pub fn new() -> Self {
+        Self { data: Vec::new() }
+    }
+
+    /// Run `f` with a cursor that writes atoms by growing this space's backing `Vec`.
+    ///
+    /// The cursor is branded with an `'id` unique to this call, so its savepoints can never be
+    /// confused with those of any other cursor, even another cursor over this same space.
+    pub fn with_cursor<R>(&mut self, f: impl for<'id> FnOnce(VecSpaceCursor<'_, 'id>) -> R) -> R {
+        f(VecSpaceCursor {
+            space: self,
+            _id: PhantomData,
+        })
+    }
+}
+
+impl Default for VecSpace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cursor writing into a [`VecSpace`], growing its backing `Vec` as needed.
+///
+/// The `'id` parameter is an invariant lifetime, unique to each cursor, that brands the
+/// [`VecSavepoint`]s this cursor produces so they can't be replayed against a different
+/// cursor. It has no meaning beyond that and is never observed; cursors are created through
+/// [`VecSpace::with_cursor`], which mints a fresh `'id` for each call.
+pub struct VecSpaceCursor<'a, 'id> {
+    space: &'a mut VecSpace,
+    _id: PhantomData<fn(&'id ()) -> &'id ()>,
+}
+
+impl<'a, 'id> VecSpaceCursor<'a, 'id> {
+    /// Append `bytes`, padding the backing `Vec` up to the next 8-byte atom alignment boundary.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> &mut [u8] {
+        let start = self.space.data.len();
+        self.space.data.extend_from_slice(bytes);
+        let padding = (8 - self.space.data.len() % 8) % 8;
+        self.space.data.resize(self.space.data.len() + padding, 0);
+        &mut self.space.data[start..start + bytes.len()]
+    }
+
+    /// Capture the current length of the backing `Vec` so a speculative write can be rolled
+    /// back later.
+    pub fn savepoint(&self) -> VecSavepoint<'a, 'id> {
+        VecSavepoint {
+            len: self.space.data.len(),
+            _cursor: PhantomData,
+            _id: PhantomData,
+        }
+    }
+
+    /// Truncate the backing `Vec` back to `savepoint`, discarding everything written since
+    /// (including any alignment padding consumed past it).
+    pub fn truncate(&mut self, savepoint: VecSavepoint<'a, 'id>) {
+        self.space.data.truncate(savepoint.len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_bytes_grows_the_backing_vec_and_pads_to_alignment() {
+        let mut space = VecSpace::new();
+        space.with_cursor(|mut cursor| {
+            cursor.write_bytes(&[1, 2, 3]);
+        });
+        assert_eq!(space.data, vec![1, 2, 3, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn truncate_rolls_back_to_a_savepoint() {
+        let mut space = VecSpace::new();
+        space.with_cursor(|mut cursor| {
+            cursor.write_bytes(&[1, 2, 3]);
+            let savepoint = cursor.savepoint();
+            cursor.write_bytes(&[4, 5, 6]);
+            assert_eq!(cursor.space.data.len(), 16);
+            cursor.truncate(savepoint);
+            assert_eq!(cursor.space.data.len(), 8);
+        });
+    }
+
+    // A savepoint taken from one `with_cursor` call cannot be passed to another: each call
+    // mints its own invariant `'id`, so reusing a savepoint across two `with_cursor` calls -
+    // even over the same `VecSpace` - fails to compile with a lifetime mismatch.
+}
@@ -0,0 +1,4 @@
+//! Port types connecting a plugin to the ports declared in its `.ttl` data.
+mod atom;
+
+pub use atom::{AtomPort, AtomPortReader, AtomPortWriter};
@@ -0,0 +1,187 @@
+//! A port type for host-connected atoms, as used by the `atom:AtomPort` port buffer class.
+use crate::port::PortType;
+use core::ffi::c_void;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+use lv2_atom::space::{AtomSpace, AtomSpaceWriter};
+use lv2_atom::{Atom, AtomHandle};
+use urid::URID;
+
+/// Port type for `atom:AtomPort` buffers.
+///
+/// LV2 simplified `atom:AtomPort` buffers to point directly at an `LV2_Atom`: the header at
+/// the start of the connected buffer gives the atom's type and size, and the bytes right
+/// after it are the atom's body. Use this as the `T` of [`InputPort`](crate::port::InputPort)
+/// or [`OutputPort`](crate::port::OutputPort) to read or write a specific atom type `A`
+/// through that buffer, the same way [`Audio`](crate::port::Audio) or
+/// [`Control`](crate::port::Control) are used for their respective port classes.
+pub struct AtomPort<A> {
+    _phantom: PhantomData<A>,
+}
+
+impl<A> PortType for AtomPort<A>
+where
+    A: for<'a> Atom<'a> + 'static,
+{
+    type InputPortType = AtomPortReader<A>;
+    type OutputPortType = AtomPortWriter<A>;
+
+    unsafe fn input_from_raw(pointer: NonNull<c_void>, _sample_count: u32) -> Self::InputPortType {
+        AtomPortReader {
+            atom: pointer.cast(),
+            _phantom: PhantomData,
+        }
+    }
+
+    unsafe fn output_from_raw(
+        pointer: NonNull<c_void>,
+        _sample_count: u32,
+    ) -> Self::OutputPortType {
+        // The host pre-fills the header's `size` field with the capacity of the buffer that
+        // follows it; the plugin must remember that before it is overwritten with the size
+        // actually written.
+        let capacity = pointer.cast::<lv2_sys::LV2_Atom>().as_ref().size as usize;
+        AtomPortWriter {
+            atom: pointer.cast(),
+            capacity,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Read handle for an [`AtomPort`], giving access to the atom the host connected.
+pub struct AtomPortReader<A> {
+    atom: NonNull<lv2_sys::LV2_Atom>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A> AtomPortReader<A>
+where
+    A: for<'a> Atom<'a>,
+{
+    /// Read the connected buffer as an atom of type `A`, identified by its mapped `urid`.
+    ///
+    /// Returns `None` if the header's declared type does not match `urid`, or if its declared
+    /// size or contents are not a valid `A`. The result borrows from `self`, so it cannot
+    /// outlive this reader, which in turn cannot outlive the `run()` call the host gave us
+    /// this buffer for.
+    pub fn read(
+        &self,
+        urid: URID<A>,
+    ) -> Option<<<A as Atom<'_>>::ReadHandle as AtomHandle<'_>>::Handle> {
+        unsafe {
+            let header = self.atom.as_ref();
+            if header.type_ != urid.get() {
+                return None;
+            }
+            let body_ptr = self.atom.as_ptr().add(1) as *const u8;
+            let body = AtomSpace::from_bytes(core::slice::from_raw_parts(
+                body_ptr,
+                header.size as usize,
+            ));
+            A::read(body)
+        }
+    }
+}
+
+/// Write handle for an [`AtomPort`], letting the plugin fill the host-provided buffer.
+pub struct AtomPortWriter<A> {
+    atom: NonNull<lv2_sys::LV2_Atom>,
+    /// Capacity of the buffer following the atom header, read from it before it gets
+    /// overwritten with the actually-written size.
+    capacity: usize,
+    _phantom: PhantomData<A>,
+}
+
+impl<A> AtomPortWriter<A>
+where
+    A: for<'a> Atom<'a>,
+{
+    /// Start writing an atom of type `A`, identified by its mapped `urid`, into the
+    /// host-provided buffer.
+    ///
+    /// The header's declared type is set to `urid` before anything else happens. The returned
+    /// writer is clamped to the capacity the host declared; allocations beyond that return
+    /// `None` instead of writing past the buffer. It borrows from `self`, so it cannot outlive
+    /// this writer, which in turn cannot outlive the `run()` call the host gave us this buffer
+    /// for. Dropping it leaves the atom header's size set to whatever was actually written.
+    pub fn init(
+        &mut self,
+        urid: URID<A>,
+    ) -> Option<<<A as Atom<'_>>::WriteHandle as AtomHandle<'_>>::Handle> {
+        unsafe {
+            self.atom.as_mut().type_ = urid.get();
+            let body_ptr = self.atom.as_ptr().add(1) as *mut u8;
+            let body = AtomSpace::from_bytes_mut(core::slice::from_raw_parts_mut(
+                body_ptr,
+                self.capacity,
+            ));
+            let writer = AtomSpaceWriter::new(body, self.atom.as_ptr());
+            A::init(writer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lv2_atom::atoms::scalar::Int;
+
+    #[repr(C)]
+    struct Buffer {
+        header: lv2_sys::LV2_Atom,
+        body: [u8; 16],
+    }
+
+    fn reader(buffer: &Buffer) -> AtomPortReader<Int> {
+        AtomPortReader {
+            atom: NonNull::from(&buffer.header).cast(),
+            _phantom: PhantomData,
+        }
+    }
+
+    #[test]
+    fn output_from_raw_captures_the_hosts_declared_capacity() {
+        let mut buffer = Buffer {
+            header: lv2_sys::LV2_Atom { size: 16, type_: 0 },
+            body: [0; 16],
+        };
+        let ptr = NonNull::from(&mut buffer).cast();
+        let writer: AtomPortWriter<Int> = unsafe { AtomPort::output_from_raw(ptr, 1) };
+        assert_eq!(writer.capacity, 16);
+    }
+
+    #[test]
+    fn read_rejects_an_atom_whose_declared_type_does_not_match_the_urid() {
+        let buffer = Buffer {
+            header: lv2_sys::LV2_Atom {
+                size: 4,
+                type_: 999,
+            },
+            body: {
+                let mut body = [0; 16];
+                body[..4].copy_from_slice(&42i32.to_ne_bytes());
+                body
+            },
+        };
+        let urid = URID::<Int>::new(1).unwrap();
+        assert!(reader(&buffer).read(urid).is_none());
+    }
+
+    #[test]
+    fn read_returns_the_value_when_the_type_matches() {
+        let urid = URID::<Int>::new(1).unwrap();
+        let buffer = Buffer {
+            header: lv2_sys::LV2_Atom {
+                size: 4,
+                type_: urid.get(),
+            },
+            body: {
+                let mut body = [0; 16];
+                body[..4].copy_from_slice(&42i32.to_ne_bytes());
+                body
+            },
+        };
+        assert_eq!(*reader(&buffer).read(urid).unwrap(), 42);
+    }
+}
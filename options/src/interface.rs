@@ -0,0 +1,233 @@
+//! The `lv2:Options` extension interface, letting a plugin respond to `get`/`set` requests.
+use crate::request::OptionRequest;
+use crate::subject::Subject;
+use crate::value::OptionValue;
+use crate::OptionType;
+use std::os::raw::c_void;
+use urid::URID;
+
+/// A single failure bit of an [`OptionsStatus`](OptionsStatus).
+///
+/// The host may batch several options into one `get`/`set` call, and the spec allows each
+/// entry in the array to fail independently. Therefore the return value is a bitfield rather
+/// than a single status code: several of these bits can be set at once.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum OptionsStatusBit {
+    /// The subject (instance, resource, blank node or port) was not recognized.
+    BadSubject = 1 << 1,
+    /// The key URID was not recognized.
+    BadKey = 1 << 2,
+    /// The value was recognized, but the type or contents were invalid.
+    BadValue = 1 << 3,
+}
+
+/// Combined status returned from a batch of `get`/`set` calls.
+///
+/// Corresponds to the bitfield returned by `LV2_Options_Interface::get`/`set`. `SUCCESS` is
+/// the all-zero value, so building up a status by OR-ing in bits as failures are encountered
+/// gives the correct result even when the whole batch succeeds.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct OptionsStatus(u32);
+
+impl OptionsStatus {
+    /// No errors occurred while handling the batch.
+    pub const SUCCESS: Self = Self(0);
+
+    /// Record a single failed option in the status.
+    pub fn fail(&mut self, bit: OptionsStatusBit) {
+        self.0 |= bit as u32;
+    }
+
+    /// The raw bitfield, as returned to the host.
+    pub fn as_raw(self) -> u32 {
+        self.0
+    }
+}
+
+/// Implemented by plugins that want to expose the `lv2:Options` extension to the host.
+///
+/// Options are matched by their key [`URID`]; a plugin typically implements this by
+/// registering a heterogeneous set of [`OptionType`]s and dispatching `get`/`set` to whichever
+/// one's URI matches the requested key.
+///
+/// # Example
+///
+/// ```
+/// use lv2_options::{OptionType, OptionsInterface};
+/// use lv2_options::request::OptionRequest;
+/// use lv2_options::value::OptionValue;
+/// use urid::*;
+///
+/// #[uri("urn:lv2_options:test:SomeIntOption")]
+/// pub struct SomeIntOption(i32);
+///
+/// impl OptionType for SomeIntOption {
+///     type AtomType = lv2_atom::atoms::scalar::Int;
+///
+///     fn from_option_value(value: &i32) -> Option<Self> {
+///         Some(Self(*value))
+///     }
+///
+///     fn as_option_value(&self) -> Option<&i32> {
+///         Some(&self.0)
+///     }
+/// }
+///
+/// struct MyPlugin {
+///     some_int: SomeIntOption,
+/// }
+///
+/// impl OptionsInterface for MyPlugin {
+///     fn get(&self, request: &OptionRequest) -> Option<OptionValue> {
+///         None
+///     }
+///
+///     fn set(&mut self, request: &OptionRequest, value: OptionValue) -> Result<bool, ()> {
+///         Ok(false)
+///     }
+/// }
+/// ```
+pub trait OptionsInterface: Sized {
+    /// Try to answer a single `get` request.
+    ///
+    /// Return `Some` with the option's current value if `request.key()` is recognized, or
+    /// `None` if it is not, in which case the caller reports `BadKey` to the host.
+    fn get(&self, request: &OptionRequest) -> Option<OptionValue>;
+
+    /// Try to apply a single `set` request.
+    ///
+    /// Return `Ok(true)` if `request.key()` was recognized and `value` was successfully
+    /// decoded and stored, `Ok(false)` if the key is unknown (the caller reports `BadKey`),
+    /// or `Err(())` if the key is known but `value` is not a valid encoding for it (the caller
+    /// reports `BadValue`).
+    fn set(&mut self, request: &OptionRequest, value: OptionValue) -> Result<bool, ()>;
+
+    /// Handle a single entry of the host's options array, updating `status` on failure.
+    fn handle_get(&self, option: &mut lv2_sys::LV2_Options_Option, status: &mut OptionsStatus) {
+        let subject = match Subject::from_raw(option.context, option.subject) {
+            Some(subject) => subject,
+            None => {
+                option.size = 0;
+                option.type_ = 0;
+                option.value = std::ptr::null();
+                status.fail(OptionsStatusBit::BadSubject);
+                return;
+            }
+        };
+        let request = OptionRequest::new(subject, URID::new(option.key));
+        match self.get(&request) {
+            Some(value) => {
+                option.size = value.size();
+                option.type_ = value.type_urid().get();
+                option.value = value.as_ptr() as *const c_void;
+            }
+            None => {
+                option.size = 0;
+                option.type_ = 0;
+                option.value = std::ptr::null();
+                status.fail(OptionsStatusBit::BadKey);
+            }
+        }
+    }
+
+    /// Handle a single entry of the host's options array, updating `status` on failure.
+    fn handle_set(&mut self, option: &lv2_sys::LV2_Options_Option, status: &mut OptionsStatus) {
+        let subject = match Subject::from_raw(option.context, option.subject) {
+            Some(subject) => subject,
+            None => {
+                status.fail(OptionsStatusBit::BadSubject);
+                return;
+            }
+        };
+        let request = OptionRequest::new(subject, URID::new(option.key));
+        let value = match URID::new(option.type_) {
+            Some(type_urid) => OptionValue::from_raw(type_urid, option.size, option.value),
+            None => {
+                status.fail(OptionsStatusBit::BadValue);
+                return;
+            }
+        };
+        match self.set(&request, value) {
+            Ok(true) => {}
+            Ok(false) => status.fail(OptionsStatusBit::BadKey),
+            Err(()) => status.fail(OptionsStatusBit::BadValue),
+        }
+    }
+}
+
+/// Iterate a null-terminated `LV2_Options_Option` array as the host passes it to `get`/`set`.
+///
+/// # Safety
+///
+/// `options` must point to a valid array of `LV2_Options_Option`s, terminated by a
+/// fully-zeroed entry, as guaranteed by the `lv2:options` host feature contract.
+unsafe fn options_iter_mut<'a>(
+    options: *mut lv2_sys::LV2_Options_Option,
+) -> impl Iterator<Item = &'a mut lv2_sys::LV2_Options_Option> {
+    let mut ptr = options;
+    std::iter::from_fn(move || {
+        let option = &mut *ptr;
+        if option.key == 0 && option.value.is_null() {
+            None
+        } else {
+            let current = ptr;
+            ptr = ptr.add(1);
+            Some(&mut *current)
+        }
+    })
+}
+
+/// Implement the raw `LV2_Options_Interface::get` callback for an [`OptionsInterface`].
+///
+/// # Safety
+///
+/// Must only be called by the host through the extension data descriptor, with `handle`
+/// pointing to a live `T` and `options` pointing to a null-terminated options array.
+pub unsafe extern "C" fn extern_get<T: OptionsInterface>(
+    handle: lv2_sys::LV2_Handle,
+    options: *mut lv2_sys::LV2_Options_Option,
+) -> u32 {
+    let instance = &*(handle as *const T);
+    let mut status = OptionsStatus::SUCCESS;
+    for option in options_iter_mut(options) {
+        instance.handle_get(option, &mut status);
+    }
+    status.as_raw()
+}
+
+/// Implement the raw `LV2_Options_Interface::set` callback for an [`OptionsInterface`].
+///
+/// # Safety
+///
+/// Must only be called by the host through the extension data descriptor, with `handle`
+/// pointing to a live `T` and `options` pointing to a null-terminated options array.
+pub unsafe extern "C" fn extern_set<T: OptionsInterface>(
+    handle: lv2_sys::LV2_Handle,
+    options: *const lv2_sys::LV2_Options_Option,
+) -> u32 {
+    let instance = &mut *(handle as *mut T);
+    let mut status = OptionsStatus::SUCCESS;
+    let mut ptr = options;
+    loop {
+        let option = &*ptr;
+        if option.key == 0 && option.value.is_null() {
+            break;
+        }
+        instance.handle_set(option, &mut status);
+        ptr = ptr.add(1);
+    }
+    status.as_raw()
+}
+
+/// Build the `LV2_Options_Interface` extension-data descriptor for a plugin.
+///
+/// This is the value a plugin's `extension_data` implementation should return for the
+/// `LV2_OPTIONS__interface` URI once it implements [`OptionsInterface`].
+pub fn options_interface_descriptor<T: OptionsInterface>() -> &'static lv2_sys::LV2_Options_Interface
+{
+    &lv2_sys::LV2_Options_Interface {
+        get: Some(extern_get::<T>),
+        set: Some(extern_set::<T>),
+    }
+}
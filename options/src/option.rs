@@ -2,10 +2,13 @@ use lv2_atom::{Atom, AtomAsBytes, AtomHandle};
 use urid::UriBound;
 
 pub mod error;
+pub mod interface;
 pub mod request;
 pub mod subject;
 pub mod value;
 
+pub use interface::OptionsInterface;
+
 /// A trait representing an LV2 Option type.
 ///
 /// # Example
@@ -26,8 +29,8 @@ pub mod value;
 ///         Some(Self(*value))
 ///     }
 ///
-///     fn as_option_value(&self) -> &i32 {
-///         &self.0
+///     fn as_option_value(&self) -> Option<&i32> {
+///         Some(&self.0)
 ///     }
 /// }
 /// ```
@@ -45,16 +48,20 @@ pub trait OptionType: UriBound + Sized {
 
     /// Returns this Option's value as a reference to its Atom type.
     ///
-    /// This method is used to send the option's value to the host when it is requested.
+    /// This method is used to send the option's value to the host when it is requested. A
+    /// `None` return means the option currently has no value, which is encoded to the host as
+    /// a null, zero-size entry rather than attempting to read one.
     fn as_option_value(
         &self,
-    ) -> <<<Self as OptionType>::AtomType as Atom>::ReadHandle as AtomHandle>::Handle;
+    ) -> Option<<<<Self as OptionType>::AtomType as Atom>::ReadHandle as AtomHandle>::Handle>;
 }
 
-/*
 impl<O: OptionType> OptionType for Option<O> {
     type AtomType = O::AtomType;
 
+    /// Never fails outright: a value that fails to decode as `O` is reported as `Some(None)`,
+    /// i.e. the option is present but unusable, since `Option<O>` already has a variant to
+    /// represent that the option has no usable value.
     fn from_option_value(
         value: <<<Self as OptionType>::AtomType as Atom>::ReadHandle as AtomHandle>::Handle,
     ) -> Option<Self> {
@@ -63,7 +70,7 @@ impl<O: OptionType> OptionType for Option<O> {
 
     fn as_option_value(
         &self,
-    ) -> <<<Self as OptionType>::AtomType as Atom>::ReadHandle as AtomHandle>::Handle {
-        todo!()
+    ) -> Option<<<<Self as OptionType>::AtomType as Atom>::ReadHandle as AtomHandle>::Handle> {
+        self.as_ref().and_then(O::as_option_value)
     }
-}*/
\ No newline at end of file
+}
\ No newline at end of file